@@ -0,0 +1,78 @@
+//! AWS deploy target - generates a SAM `template.yaml` for Lambda + S3.
+
+use std::path::PathBuf;
+
+use clap::Args;
+
+use super::names;
+
+#[derive(Args)]
+pub struct AwsArgs {
+    /// S3 bucket that receives Parquet output (defaults to a generated name)
+    #[arg(long)]
+    pub bucket: Option<String>,
+
+    /// AWS region to deploy into
+    #[arg(long, default_value = "us-east-1")]
+    pub region: String,
+
+    /// Where to write the generated template.yaml
+    #[arg(long, default_value = "template.yaml")]
+    pub output: PathBuf,
+
+    /// Re-generate template.yaml on every change to the schema or config
+    /// file instead of generating once and exiting
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Path to watch under `--watch`, in addition to this crate's own
+    /// source directory; repeat to watch several files/directories
+    #[arg(long)]
+    pub watch_path: Vec<PathBuf>,
+}
+
+pub fn run(args: &AwsArgs) -> anyhow::Result<()> {
+    write_config(args)
+}
+
+/// Render and write `template.yaml`. Safe to call repeatedly, e.g. from the
+/// `--watch` loop.
+pub fn write_config(args: &AwsArgs) -> anyhow::Result<()> {
+    let bucket = args
+        .bucket
+        .clone()
+        .unwrap_or_else(|| names::resource_name("s3-bucket"));
+
+    let template = render_template(args, &bucket);
+    std::fs::write(&args.output, template)?;
+    println!("wrote {}", args.output.display());
+
+    Ok(())
+}
+
+fn render_template(args: &AwsArgs, bucket: &str) -> String {
+    format!(
+        r#"AWSTemplateFormatVersion: '2010-09-09'
+Transform: AWS::Serverless-2016-10-31
+Description: otlp2parquet OTLP -> Parquet -> S3 sink
+
+Resources:
+  SpanBucket:
+    Type: AWS::S3::Bucket
+    Properties:
+      BucketName: {bucket}
+
+  IngestFunction:
+    Type: AWS::Serverless::Function
+    Properties:
+      Handler: bootstrap
+      Runtime: provided.al2
+      Environment:
+        Variables:
+          OTLP2PARQUET_BUCKET: {bucket}
+          OTLP2PARQUET_REGION: {region}
+"#,
+        bucket = bucket,
+        region = args.region,
+    )
+}
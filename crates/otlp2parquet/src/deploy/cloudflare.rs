@@ -0,0 +1,345 @@
+//! Cloudflare deploy target - generates, and optionally provisions, a
+//! Worker + R2 pipeline for OTLP -> Parquet -> R2.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+
+use super::names;
+
+#[derive(Args)]
+pub struct CloudflareArgs {
+    /// Cloudflare account ID
+    #[arg(long, env = "CF_ACCOUNT_ID")]
+    pub account_id: String,
+
+    /// Zone ID to bind `--route` in, required when `--route` is set
+    /// together with `--deploy`
+    #[arg(long, env = "CF_ZONE_ID")]
+    pub zone_id: Option<String>,
+
+    /// R2 bucket that receives Parquet output (defaults to a generated name)
+    #[arg(long)]
+    pub bucket: Option<String>,
+
+    /// Name of the deployed Worker
+    #[arg(long, default_value = "otlp2parquet-ingest")]
+    pub worker_name: String,
+
+    /// Where to write the generated wrangler.toml
+    #[arg(long, default_value = "wrangler.toml")]
+    pub output: PathBuf,
+
+    /// Actually provision the pipeline via the Cloudflare API instead of
+    /// only writing wrangler.toml: create the R2 bucket if it doesn't
+    /// already exist, then upload the Worker script bound to it.
+    #[arg(long)]
+    pub deploy: bool,
+
+    /// Path to the Worker script to upload when `--deploy` is set
+    #[arg(long, default_value = "src/worker.js")]
+    pub script: PathBuf,
+
+    /// Ingest route pattern to bind the Worker to (e.g.
+    /// `otlp.example.com/v1/*`)
+    #[arg(long)]
+    pub route: Option<String>,
+
+    /// Cron schedule for the scheduled flush handler (e.g. `*/5 * * * *`),
+    /// which flushes partially filled Parquet row groups to R2 on a timer
+    /// rather than only on request volume
+    #[arg(long)]
+    pub flush_cron: Option<String>,
+
+    /// Re-generate wrangler.toml on every change to the schema or config
+    /// file instead of generating once and exiting
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Path to watch under `--watch`, in addition to this crate's own
+    /// source directory; repeat to watch several files/directories
+    #[arg(long)]
+    pub watch_path: Vec<PathBuf>,
+}
+
+pub fn run(args: &CloudflareArgs) -> anyhow::Result<()> {
+    write_config(args)?;
+
+    if args.deploy {
+        deploy(args, &bucket_name(args))?;
+    }
+
+    Ok(())
+}
+
+/// Render and write `wrangler.toml` only, without provisioning anything
+/// live. Safe to call repeatedly, e.g. from the `--watch` loop, since
+/// `--deploy` should stay a one-shot action.
+pub fn write_config(args: &CloudflareArgs) -> anyhow::Result<()> {
+    let bucket = bucket_name(args);
+    let toml = render_wrangler_toml(args, &bucket);
+    std::fs::write(&args.output, toml)?;
+    println!("wrote {}", args.output.display());
+    Ok(())
+}
+
+fn bucket_name(args: &CloudflareArgs) -> String {
+    args.bucket
+        .clone()
+        .unwrap_or_else(|| names::resource_name("r2-bucket"))
+}
+
+fn render_wrangler_toml(args: &CloudflareArgs, bucket: &str) -> String {
+    use std::fmt::Write as _;
+
+    let mut toml = format!(
+        r#"name = "{worker_name}"
+main = "src/worker.js"
+compatibility_date = "2024-01-01"
+
+[[r2_buckets]]
+binding = "SPANS"
+bucket_name = "{bucket}"
+"#,
+        worker_name = args.worker_name,
+    );
+
+    if let Some(route) = &args.route {
+        let _ = write!(
+            toml,
+            r#"
+[[routes]]
+pattern = "{route}"
+"#
+        );
+    }
+
+    if let Some(cron) = &args.flush_cron {
+        let _ = write!(
+            toml,
+            r#"
+[triggers]
+crons = ["{cron}"]
+"#
+        );
+    }
+
+    toml
+}
+
+/// Provision the pipeline end-to-end: create the R2 bucket if it doesn't
+/// already exist, then push the Worker script bound to it. This is the
+/// live-deploy counterpart to `write_config`, which only renders
+/// `wrangler.toml`.
+fn deploy(args: &CloudflareArgs, bucket: &str) -> anyhow::Result<()> {
+    use cloudflare::framework::{
+        auth::Credentials, client::blocking_api::HttpApiClient, Environment, HttpApiClientConfig,
+    };
+
+    if args.route.is_some() && args.zone_id.is_none() {
+        anyhow::bail!("--zone-id (or CF_ZONE_ID) is required to provision --route via --deploy");
+    }
+
+    let api_token = std::env::var("CF_API_TOKEN")
+        .context("CF_API_TOKEN must be set to use --deploy")?;
+    let client = HttpApiClient::new(
+        Credentials::UserAuthToken {
+            token: api_token.clone(),
+        },
+        HttpApiClientConfig::default(),
+        Environment::Production,
+    )?;
+
+    ensure_bucket(&client, &args.account_id, bucket)?;
+    publish_script(&api_token, &args.account_id, &args.worker_name, &args.script, bucket)?;
+
+    if let Some(cron) = &args.flush_cron {
+        apply_cron_schedule(&api_token, &args.account_id, &args.worker_name, cron)?;
+    }
+
+    if let (Some(route), Some(zone_id)) = (&args.route, &args.zone_id) {
+        apply_route(&api_token, zone_id, route, &args.worker_name)?;
+    }
+
+    println!("deployed worker `{}` bound to r2 bucket `{bucket}`", args.worker_name);
+
+    Ok(())
+}
+
+fn ensure_bucket(
+    client: &cloudflare::framework::client::blocking_api::HttpApiClient,
+    account_id: &str,
+    bucket: &str,
+) -> anyhow::Result<()> {
+    use cloudflare::endpoints::r2::{CreateBucket, CreateBucketParams, ListBuckets};
+
+    let existing = client
+        .request(&ListBuckets {
+            account_identifier: account_id,
+        })
+        .context("listing r2 buckets")?
+        .result;
+
+    if existing.buckets.iter().any(|b| b.name == bucket) {
+        return Ok(());
+    }
+
+    client
+        .request(&CreateBucket {
+            account_identifier: account_id,
+            params: CreateBucketParams {
+                name: bucket.to_string(),
+            },
+        })
+        .with_context(|| format!("creating r2 bucket `{bucket}`"))?;
+
+    Ok(())
+}
+
+/// Upload the Worker script via the multipart script-upload endpoint, the
+/// same flow `wrangler publish` uses: a `script` part with the JS source and
+/// a `metadata` part declaring the R2 bucket binding. Takes the same
+/// `api_token` used to build the `cloudflare` crate client in `deploy`,
+/// since the crate itself has no Workers script-upload endpoint.
+fn publish_script(
+    api_token: &str,
+    account_id: &str,
+    worker_name: &str,
+    script_path: &std::path::Path,
+    bucket: &str,
+) -> anyhow::Result<()> {
+    let script = std::fs::read_to_string(script_path)
+        .with_context(|| format!("reading worker script {}", script_path.display()))?;
+
+    let metadata = serde_json::json!({
+        "body_part": "script",
+        "bindings": [{
+            "type": "r2_bucket",
+            "name": "SPANS",
+            "bucket_name": bucket,
+        }],
+        "compatibility_date": "2024-01-01",
+    });
+
+    let form = reqwest::blocking::multipart::Form::new()
+        .text("metadata", metadata.to_string())
+        .part(
+            "script",
+            reqwest::blocking::multipart::Part::text(script)
+                .file_name("worker.js")
+                .mime_str("application/javascript")?,
+        );
+
+    let url = format!(
+        "https://api.cloudflare.com/client/v4/accounts/{account_id}/workers/scripts/{worker_name}"
+    );
+
+    let res = reqwest::blocking::Client::new()
+        .put(url)
+        .bearer_auth(api_token)
+        .multipart(form)
+        .send()
+        .context("uploading worker script")?;
+
+    if !res.status().is_success() {
+        let status = res.status();
+        let body = res.text().unwrap_or_default();
+        anyhow::bail!("worker script upload failed ({status}): {body}");
+    }
+
+    Ok(())
+}
+
+/// Set the worker's scheduled-event cron trigger via the Workers schedules
+/// endpoint, which the `cloudflare` crate doesn't cover.
+fn apply_cron_schedule(
+    api_token: &str,
+    account_id: &str,
+    worker_name: &str,
+    cron: &str,
+) -> anyhow::Result<()> {
+    let url = format!(
+        "https://api.cloudflare.com/client/v4/accounts/{account_id}/workers/scripts/{worker_name}/schedules"
+    );
+    let res = reqwest::blocking::Client::new()
+        .put(url)
+        .bearer_auth(api_token)
+        .json(&serde_json::json!([{ "cron": cron }]))
+        .send()
+        .context("setting worker cron schedule")?;
+
+    if !res.status().is_success() {
+        let status = res.status();
+        let body = res.text().unwrap_or_default();
+        anyhow::bail!("setting cron schedule failed ({status}): {body}");
+    }
+
+    Ok(())
+}
+
+/// Bind the worker to a route via the zone routes endpoint, which the
+/// `cloudflare` crate doesn't cover.
+fn apply_route(api_token: &str, zone_id: &str, route: &str, worker_name: &str) -> anyhow::Result<()> {
+    let url = format!("https://api.cloudflare.com/client/v4/zones/{zone_id}/workers/routes");
+    let res = reqwest::blocking::Client::new()
+        .post(url)
+        .bearer_auth(api_token)
+        .json(&serde_json::json!({ "pattern": route, "script": worker_name }))
+        .send()
+        .context("creating worker route")?;
+
+    if !res.status().is_success() {
+        let status = res.status();
+        let body = res.text().unwrap_or_default();
+        anyhow::bail!("creating worker route failed ({status}): {body}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_args() -> CloudflareArgs {
+        CloudflareArgs {
+            account_id: "acct".to_string(),
+            zone_id: None,
+            bucket: None,
+            worker_name: "otlp2parquet-ingest".to_string(),
+            output: PathBuf::from("wrangler.toml"),
+            deploy: false,
+            script: PathBuf::from("src/worker.js"),
+            route: None,
+            flush_cron: None,
+            watch: false,
+            watch_path: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn renders_without_route_or_cron() {
+        let toml = render_wrangler_toml(&base_args(), "otlp2parquet-r2-bucket");
+        assert!(!toml.contains("[[routes]]"));
+        assert!(!toml.contains("[triggers]"));
+    }
+
+    #[test]
+    fn renders_route_when_set() {
+        let mut args = base_args();
+        args.route = Some("otlp.example.com/v1/*".to_string());
+        let toml = render_wrangler_toml(&args, "otlp2parquet-r2-bucket");
+        assert!(toml.contains("[[routes]]"));
+        assert!(toml.contains(r#"pattern = "otlp.example.com/v1/*""#));
+    }
+
+    #[test]
+    fn renders_flush_cron_when_set() {
+        let mut args = base_args();
+        args.flush_cron = Some("*/5 * * * *".to_string());
+        let toml = render_wrangler_toml(&args, "otlp2parquet-r2-bucket");
+        assert!(toml.contains("[triggers]"));
+        assert!(toml.contains(r#"crons = ["*/5 * * * *"]"#));
+    }
+}
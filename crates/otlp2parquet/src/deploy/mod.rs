@@ -2,11 +2,14 @@
 //!
 //! Usage: `otlp2parquet create cloudflare` or `otlp2parquet create cf`
 //!        `otlp2parquet create aws`
+//!        `otlp2parquet create openstack`
 
 mod names;
+mod watch;
 
 pub mod aws;
 pub mod cloudflare;
+pub mod openstack;
 
 use clap::Subcommand;
 
@@ -17,13 +20,54 @@ pub enum DeployCommand {
     Cloudflare(cloudflare::CloudflareArgs),
     /// Generate template.yaml for AWS Lambda + S3/S3 Tables
     Aws(aws::AwsArgs),
+    /// Generate deployment config for an OpenStack Swift object-storage backend
+    Openstack(openstack::OpenstackArgs),
 }
 
 impl DeployCommand {
     pub fn run(self) -> anyhow::Result<()> {
         match self {
-            DeployCommand::Cloudflare(args) => cloudflare::run(args),
-            DeployCommand::Aws(args) => aws::run(args),
+            DeployCommand::Cloudflare(args) => {
+                cloudflare::run(&args)?;
+                if args.watch {
+                    let paths = watch_paths(&args.watch_path, Vec::new());
+                    watch::run(&paths, move || cloudflare::write_config(&args))
+                } else {
+                    Ok(())
+                }
+            }
+            DeployCommand::Aws(args) => {
+                aws::run(&args)?;
+                if args.watch {
+                    let paths = watch_paths(&args.watch_path, Vec::new());
+                    watch::run(&paths, move || aws::write_config(&args))
+                } else {
+                    Ok(())
+                }
+            }
+            DeployCommand::Openstack(args) => {
+                openstack::run(&args)?;
+                if args.watch {
+                    let extra = openstack::find_clouds_yaml().into_iter().collect();
+                    let paths = watch_paths(&args.watch_path, extra);
+                    watch::run(&paths, move || openstack::write_config(&args))
+                } else {
+                    Ok(())
+                }
+            }
         }
     }
 }
+
+/// Paths to watch under `--watch`: the crate's own defaults, plus any
+/// user-supplied `--watch-path` overrides, plus `extra` paths a given
+/// target needs watched beyond its source (e.g. OpenStack's `clouds.yaml`).
+fn watch_paths(
+    overrides: &[std::path::PathBuf],
+    extra: Vec<std::path::PathBuf>,
+) -> Vec<std::path::PathBuf> {
+    let mut paths = watch::default_paths();
+    paths.extend(overrides.iter().cloned());
+    paths.extend(extra);
+    paths
+}
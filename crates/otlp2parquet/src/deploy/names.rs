@@ -0,0 +1,11 @@
+//! Resource naming shared by deploy targets.
+//!
+//! Every deploy target needs to name the bucket/container it provisions.
+//! Centralizing the scheme here keeps names consistent (and readable) across
+//! Cloudflare, AWS, and any future backend.
+
+/// Derive the default object-storage resource name for `component` (e.g.
+/// `"r2-bucket"`, `"s3-bucket"`), scoped to this crate's deploy output.
+pub(crate) fn resource_name(component: &str) -> String {
+    format!("otlp2parquet-{component}")
+}
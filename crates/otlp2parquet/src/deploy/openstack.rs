@@ -0,0 +1,258 @@
+//! OpenStack deploy target - generates deployment config for a Swift
+//! object-storage backend, for users running private/OpenStack-based infra.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::Args;
+use serde::Deserialize;
+
+use super::names;
+
+#[derive(Args)]
+pub struct OpenstackArgs {
+    /// Named cloud from `clouds.yaml` (defaults to `$OS_CLOUD`)
+    #[arg(long, env = "OS_CLOUD")]
+    pub cloud: String,
+
+    /// Swift container that receives Parquet output (defaults to a
+    /// generated name)
+    #[arg(long)]
+    pub container: Option<String>,
+
+    /// Where to write the generated deploy config
+    #[arg(long, default_value = "openstack.yaml")]
+    pub output: PathBuf,
+
+    /// Re-generate the deploy config on every change to the schema or
+    /// config file instead of generating once and exiting
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Path to watch under `--watch`, in addition to this crate's own
+    /// source directory and the resolved `clouds.yaml`; repeat to watch
+    /// several files/directories
+    #[arg(long)]
+    pub watch_path: Vec<PathBuf>,
+}
+
+/// A single `clouds.yaml` entry, trimmed to what this deploy target needs.
+#[derive(Deserialize)]
+struct Cloud {
+    auth: CloudAuth,
+    region_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CloudAuth {
+    auth_url: String,
+    project_name: String,
+    username: Option<String>,
+    password: Option<String>,
+    application_credential_id: Option<String>,
+    application_credential_secret: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CloudsFile {
+    clouds: std::collections::HashMap<String, Cloud>,
+}
+
+pub fn run(args: &OpenstackArgs) -> anyhow::Result<()> {
+    write_config(args)
+}
+
+/// Render and write the deploy config. Safe to call repeatedly, e.g. from
+/// the `--watch` loop.
+pub fn write_config(args: &OpenstackArgs) -> anyhow::Result<()> {
+    let clouds_path =
+        find_clouds_yaml().context("could not locate clouds.yaml (checked ./, ~/.config/openstack/, /etc/openstack/)")?;
+    let cloud = load_cloud(&clouds_path, &args.cloud)?;
+
+    let container = args
+        .container
+        .clone()
+        .unwrap_or_else(|| names::resource_name("swift-container"));
+
+    let config = render_config(&args.cloud, &cloud, &container);
+    write_private(&args.output, &config)?;
+    println!("wrote {}", args.output.display());
+
+    Ok(())
+}
+
+/// Write the generated config with owner-only permissions, since it embeds
+/// the cloud's password or application credential secret in plain text.
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &str) -> anyhow::Result<()> {
+    use std::fs::OpenOptions;
+    use std::io::Write as _;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    // `mode` only applies when the file is created; tighten an existing one too.
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("setting permissions on {}", path.display()))?;
+    file.write_all(contents.as_bytes())
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &str) -> anyhow::Result<()> {
+    std::fs::write(path, contents).with_context(|| format!("writing {}", path.display()))
+}
+
+/// Search path for `clouds.yaml`, per the standard OpenStack client
+/// convention: current directory, then the user config dir, then
+/// `/etc/openstack`.
+pub(super) fn find_clouds_yaml() -> Option<PathBuf> {
+    let mut candidates = vec![PathBuf::from("clouds.yaml")];
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".config/openstack/clouds.yaml"));
+    }
+    candidates.push(PathBuf::from("/etc/openstack/clouds.yaml"));
+
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+fn load_cloud(clouds_path: &Path, cloud_name: &str) -> anyhow::Result<Cloud> {
+    let raw = std::fs::read_to_string(clouds_path)
+        .with_context(|| format!("reading {}", clouds_path.display()))?;
+    let mut parsed: CloudsFile =
+        serde_yaml::from_str(&raw).with_context(|| format!("parsing {}", clouds_path.display()))?;
+
+    parsed
+        .clouds
+        .remove(cloud_name)
+        .with_context(|| format!("cloud `{cloud_name}` not found in {}", clouds_path.display()))
+}
+
+fn render_config(cloud_name: &str, cloud: &Cloud, container: &str) -> String {
+    use std::fmt::Write as _;
+
+    let mut config = format!(
+        r#"# otlp2parquet OTLP -> Parquet -> Swift sink
+cloud: {cloud_name}
+auth_url: {auth_url}
+project_name: {project_name}
+region_name: {region_name}
+container: {container}
+"#,
+        auth_url = cloud.auth.auth_url,
+        project_name = cloud.auth.project_name,
+        region_name = cloud.region_name.as_deref().unwrap_or(""),
+    );
+
+    match (&cloud.auth.application_credential_id, &cloud.auth.application_credential_secret) {
+        (Some(id), Some(secret)) => {
+            let _ = writeln!(config, "application_credential_id: {id}");
+            let _ = writeln!(config, "application_credential_secret: {secret}");
+        }
+        _ => {
+            if let Some(username) = &cloud.auth.username {
+                let _ = writeln!(config, "username: {username}");
+            }
+            if let Some(password) = &cloud.auth.password {
+                let _ = writeln!(config, "password: {password}");
+            }
+        }
+    }
+
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cloud_with(auth: CloudAuth, region_name: Option<&str>) -> Cloud {
+        Cloud {
+            auth,
+            region_name: region_name.map(str::to_string),
+        }
+    }
+
+    fn password_auth() -> CloudAuth {
+        CloudAuth {
+            auth_url: "https://keystone.example.com/v3".to_string(),
+            project_name: "spans".to_string(),
+            username: Some("otlp".to_string()),
+            password: Some("hunter2".to_string()),
+            application_credential_id: None,
+            application_credential_secret: None,
+        }
+    }
+
+    #[test]
+    fn renders_region_when_set() {
+        let cloud = cloud_with(password_auth(), Some("RegionOne"));
+        let config = render_config("mycloud", &cloud, "otlp2parquet-swift-container");
+        assert!(config.contains("region_name: RegionOne"));
+    }
+
+    #[test]
+    fn renders_blank_region_when_missing() {
+        let cloud = cloud_with(password_auth(), None);
+        let config = render_config("mycloud", &cloud, "otlp2parquet-swift-container");
+        assert!(config.contains("region_name: \n"));
+    }
+
+    #[test]
+    fn renders_username_and_password() {
+        let cloud = cloud_with(password_auth(), Some("RegionOne"));
+        let config = render_config("mycloud", &cloud, "otlp2parquet-swift-container");
+        assert!(config.contains("username: otlp"));
+        assert!(config.contains("password: hunter2"));
+        assert!(!config.contains("application_credential_id"));
+    }
+
+    #[test]
+    fn prefers_application_credential_over_password() {
+        let auth = CloudAuth {
+            application_credential_id: Some("app-id".to_string()),
+            application_credential_secret: Some("app-secret".to_string()),
+            ..password_auth()
+        };
+        let cloud = cloud_with(auth, Some("RegionOne"));
+        let config = render_config("mycloud", &cloud, "otlp2parquet-swift-container");
+        assert!(config.contains("application_credential_id: app-id"));
+        assert!(config.contains("application_credential_secret: app-secret"));
+        assert!(!config.contains("username:"));
+    }
+
+    #[test]
+    fn load_cloud_finds_named_entry() {
+        let dir = std::env::temp_dir().join(format!(
+            "otlp2parquet-test-clouds-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let clouds_path = dir.join("clouds.yaml");
+        std::fs::write(
+            &clouds_path,
+            r#"
+clouds:
+  mycloud:
+    auth:
+      auth_url: https://keystone.example.com/v3
+      project_name: spans
+      username: otlp
+      password: hunter2
+    region_name: RegionOne
+"#,
+        )
+        .unwrap();
+
+        let cloud = load_cloud(&clouds_path, "mycloud").unwrap();
+        assert_eq!(cloud.auth.auth_url, "https://keystone.example.com/v3");
+        assert_eq!(cloud.region_name.as_deref(), Some("RegionOne"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+//! Debounced filesystem watcher backing `--watch`: re-run a target's
+//! generator whenever the Parquet schema definition or an input config file
+//! changes, instead of generating once and exiting.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+
+/// Coalesce bursts of filesystem events (editors commonly emit several
+/// writes per save) into a single regeneration.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Paths watched for every deploy target: the schema that drives the
+/// generated resource definitions, plus anything under the crate source
+/// that isn't build output. Resolved relative to this crate's own manifest
+/// at compile time (not the process's current working directory), so
+/// `--watch` works the same whether the binary is run from the checkout
+/// root or installed elsewhere.
+pub(super) fn default_paths() -> Vec<PathBuf> {
+    vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src")]
+}
+
+/// Block on the watched paths and call `regenerate` every time they change,
+/// until the process is interrupted. Unlike a one-shot generate, this never
+/// calls `regenerate` up front - callers generate once themselves before
+/// entering the loop, so e.g. a live `--deploy` action stays a one-time
+/// thing even when `--watch` is also set.
+pub(super) fn run(
+    paths: &[PathBuf],
+    mut regenerate: impl FnMut() -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    for path in paths {
+        watcher.watch(path, RecursiveMode::Recursive)?;
+    }
+
+    println!("watching for changes (ctrl-c to stop)...");
+    loop {
+        wait_for_burst(&rx, DEBOUNCE)?;
+
+        if let Err(err) = regenerate() {
+            eprintln!("regeneration failed: {err:#}");
+        }
+    }
+}
+
+/// Block until at least one message arrives on `rx`, then drain any further
+/// messages that arrive within `debounce` so a burst of events (editors
+/// commonly emit several writes per save) collapses into a single return.
+fn wait_for_burst<T>(rx: &mpsc::Receiver<T>, debounce: Duration) -> Result<(), mpsc::RecvError> {
+    rx.recv()?;
+    while rx.recv_timeout(debounce).is_ok() {}
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coalesces_a_burst_into_one_wakeup() {
+        let (tx, rx) = mpsc::channel();
+        for _ in 0..5 {
+            tx.send(()).unwrap();
+        }
+
+        wait_for_burst(&rx, Duration::from_millis(50)).unwrap();
+
+        assert!(rx.try_recv().is_err(), "burst should have been fully drained");
+    }
+
+    #[test]
+    fn wakes_again_for_a_later_burst() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(()).unwrap();
+        wait_for_burst(&rx, Duration::from_millis(50)).unwrap();
+
+        tx.send(()).unwrap();
+        wait_for_burst(&rx, Duration::from_millis(50)).unwrap();
+
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn errors_once_senders_are_dropped() {
+        let (tx, rx) = mpsc::channel::<()>();
+        drop(tx);
+        assert!(wait_for_burst(&rx, Duration::from_millis(50)).is_err());
+    }
+}